@@ -1,7 +1,18 @@
 #![allow(unused, unused_variables)]
 
+use std::cell::Cell;
+
 use crate::token::*;
 
+thread_local! {
+    // Decimal places used when displaying numbers, None for full precision
+    static NUMBER_PRECISION: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+pub fn set_number_precision(precision: Option<usize>) {
+    NUMBER_PRECISION.with(|p| p.set(precision));
+}
+
 pub enum LitValue {
     Number(f64),
     Str(String),
@@ -13,7 +24,10 @@ pub enum LitValue {
 impl LitValue {
     pub fn to_string(&self) -> String {
         match self {
-            LitValue::Number(n) => return format!("{n}"),
+            LitValue::Number(n) => return match NUMBER_PRECISION.with(|p| p.get()) {
+                Some(precision) => format!("{n:.precision$}"),
+                None => format!("{n}")
+            },
             LitValue::Str(s) => return s.to_string(),
             LitValue::True(_) => return String::from("true") ,
             LitValue::False(_) => return String::from("false"),
@@ -21,17 +35,26 @@ impl LitValue {
         }
     }
 
+    // Like to_string, but strings keep their quotes
+    pub fn to_repr(&self) -> String {
+        match self {
+            LitValue::Str(s) => format!("{s:?}"),
+            _ => self.to_string()
+        }
+    }
+
     pub fn from_token(token: Token) -> Self {
         match token.get_type() {
-            TokenType::Number => Self::Number(match token.get_lexeme().parse::<f64>() {
-                Ok(f) => f,
-                Err(_) => panic!("Could not parse as Number")
+            TokenType::Number => Self::Number(match token.get_literal() {
+                Literal::Numeric(f) => f,
+                _ => panic!("Could not parse as Number")
             }),
 
-            TokenType::String => Self::Str(token.get_lexeme().to_string()),
+            TokenType::String => Self::Str(token.get_literal().to_string().to_owned()),
             TokenType::Identifier => Self::Str(token.get_lexeme().to_string()),
             TokenType::True => LitValue::True(true),
             TokenType::False => LitValue::False(false),
+            TokenType::Nil => LitValue::Nil,
             _ => panic!("Could Not get literal from {}", token.to_string())
         }
     } 
@@ -42,24 +65,36 @@ pub enum Expr {
     Grouping {expr: Box<Expr>},
     Literal {literal: LitValue},
     Unary {operator: Token, right: Box<Expr>},
+    Ternary {condition: Box<Expr>, then: Box<Expr>, otherwise: Box<Expr>},
     Operator {token: Token}
 }
 
 impl Expr {
 
     pub fn to_string(&self) -> String {
+        self.render(false)
+    }
+
+    pub fn to_repr(&self) -> String {
+        self.render(true)
+    }
+
+    fn render(&self, repr: bool) -> String {
         match self {
             Expr::Binary { left, operator, right } => {
-                format!("{} {} {}", (*left).to_string(), operator.get_lexeme(), (*right).to_string())
+                format!("{} {} {}", (*left).render(repr), operator.get_lexeme(), (*right).render(repr))
             },
             Expr::Grouping { expr } => {
-                format!("({})", (*expr).to_string())
+                format!("({})", (*expr).render(repr))
             },
             Expr::Literal { literal } => {
-                format!("{}", literal.to_string())
+                if repr {literal.to_repr()} else {literal.to_string()}
             },
             Expr::Unary { operator, right } => { 
-                format!("{}{}",operator.get_lexeme(), (*right).to_string())
+                format!("{}{}",operator.get_lexeme(), (*right).render(repr))
+            },
+            Expr::Ternary { condition, then, otherwise } => {
+                format!("{} ? {} : {}", (*condition).render(repr), (*then).render(repr), (*otherwise).render(repr))
             },
             Expr::Operator { token } => {
                 token.get_lexeme().to_string()
@@ -83,6 +118,10 @@ impl Expr {
         Self::Unary { operator, right: Box::from(right) }
     }
 
+    pub fn new_ternary(condition: Expr, then: Expr, otherwise: Expr) -> Self {
+        Self::Ternary { condition: Box::from(condition), then: Box::from(then), otherwise: Box::from(otherwise) }
+    }
+
     pub fn new_operator(token: Token) -> Self {
         Self::Operator { token }
     }
@@ -98,10 +137,36 @@ mod tests {
         
         let soln1 = String::from("-123 * (45.67)");
 
-        let expr1 = Expr::Binary { left: Box::new(Expr::Unary { operator: Token::new(TokenType::Minus, "-", Literal::Null, 1), right: (Box::new(Expr::Literal { literal: LitValue::Number(123.0) }))}), operator: Token::new(TokenType::Star, "*", Literal::Null, 1), right: Box::new(Expr::Grouping { expr: Box::new(Expr::Literal { literal: LitValue::Number(45.67) }) })};
+        let expr1 = Expr::Binary { left: Box::new(Expr::Unary { operator: Token::new(TokenType::Minus, "-", Literal::Null, 1, 1), right: (Box::new(Expr::Literal { literal: LitValue::Number(123.0) }))}), operator: Token::new(TokenType::Star, "*", Literal::Null, 1, 6), right: Box::new(Expr::Grouping { expr: Box::new(Expr::Literal { literal: LitValue::Number(45.67) }) })};
 
         let res1 = expr1.to_string();
         assert_eq!(res1, soln1);
 
     }
+
+    #[test]
+    fn test_to_repr() {
+        let expr = Expr::new_grouping(Expr::new_literal(LitValue::Str(String::from("hi"))));
+        assert_eq!(expr.to_string(), "(hi)");
+        assert_eq!(expr.to_repr(), "(\"hi\")");
+        assert_eq!(LitValue::Number(1.5).to_repr(), "1.5");
+    }
+
+    #[test]
+    fn test_number_from_token() {
+        let token = Token::new(TokenType::Number, "0xFF", Literal::Numeric(255.0), 1, 1);
+        assert_eq!(LitValue::from_token(token).to_string(), "255");
+    }
+
+    #[test]
+    fn test_number_precision() {
+        let number = LitValue::Number(2.46813579);
+        assert_eq!(number.to_string(), "2.46813579");
+
+        set_number_precision(Some(2));
+        assert_eq!(number.to_string(), "2.47");
+
+        set_number_precision(None);
+        assert_eq!(number.to_string(), "2.46813579");
+    }
 }
\ No newline at end of file
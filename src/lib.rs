@@ -0,0 +1,36 @@
+pub mod token;
+pub mod scanner;
+pub mod expr;
+pub mod parser;
+
+pub use scanner::Scanner;
+pub use parser::Parser;
+pub use expr::{Expr, LitValue, set_number_precision};
+pub use token::{Token, TokenType, Literal};
+
+/// Scans and parses `src` into an expression.
+/// Scan errors and parse errors are both returned as an `Err`.
+pub fn run(src: &str) -> Result<Expr, String> {
+    let tokens = Scanner::new(src).into_tokens()?;
+    Parser::new(tokens).parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        assert_eq!(run("1 + 2 * 3").unwrap().to_string(), "1 + 2 * 3");
+        assert!(run("\"unterminated").is_err());
+        assert_eq!(run("nil").unwrap().to_string(), "nil");
+        assert_eq!(run("1\t+ 2").unwrap().to_string(), "1 + 2");
+    }
+
+    #[test]
+    fn test_run_parse_errors() {
+        assert_eq!(run("").err().unwrap(), "Line 1, column 1: Expected expression, found end of input");
+        assert_eq!(run("(1").err().unwrap(), "Line 1, column 3: Expected ')', found end of input");
+        assert_eq!(run("1 ? 2").err().unwrap(), "Line 1, column 6: Expected ':' in conditional expression, found end of input");
+    }
+}
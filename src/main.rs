@@ -1,82 +1,252 @@
-mod token;
-mod scanner;
-mod expr;
-mod parser;
-
 use std::{
-    env,
+    env, fs,
     io::{self, BufRead, Write},
     process::exit,
 };
 
-use scanner::*;
-use token::*;
-use parser::*;
+use ic_rs_lang::set_number_precision;
+
+struct ReplConfig {
+    prompt: String,
+    // Print results with string literals quoted
+    repr: bool,
+}
+
+impl ReplConfig {
+    fn from_env() -> Self {
+        let prompt = env::var("LOX_PROMPT").unwrap_or_else(|_| String::from("> "));
+        let repr = matches!(env::var("LOX_REPR").as_deref(), Ok("1"));
+        Self { prompt, repr }
+    }
+}
 
-fn run_prompt() -> Result<(), String> {
-    let mut stdin = io::stdin().lock();
+fn run_prompt(config: &ReplConfig) -> Result<(), String> {
+    run_repl(io::stdin().lock(), io::stdout(), config)
+}
 
-    println!("Welcome to the Lox Interpreter. Press CTRL+D to exit.\n");
+fn run_repl<R: BufRead, W: Write>(mut input: R, mut output: W, config: &ReplConfig) -> Result<(), String> {
+    write_output(&mut output, "Welcome to the Lox Interpreter. Press CTRL+D to exit.\n\n")?;
 
     loop {
         let mut buffer = String::new();
-        print!("> ");
-        match io::stdout().flush() {
+        write_output(&mut output, &config.prompt)?;
+        match output.flush() {
             Ok(_) => (),
             Err(_) => return Err(String::from("Failed to clear output")),
         }
 
-        match stdin.read_line(&mut buffer) {
+        match input.read_line(&mut buffer) {
             Ok(n) => {
                 if n == 0 {
-                    println!("\nInterpreter Quit");
+                    write_output(&mut output, "\nInterpreter Quit\n")?;
                     return Ok(());
                 }
             }
             Err(_) => return Err(String::from("Failed to read input")),
         }
 
-        println!("You Entered: {buffer}");
-        run(buffer.trim());
+        write_output(&mut output, &format!("You Entered: {buffer}\n"))?;
+        if let Err(msg) = run(buffer.trim(), &mut output, config.repr) {
+            write_output(&mut output, &format!("Error {msg}\n"))?;
+        }
     }
 }
 
-fn run_file(_src: &str) -> Result<(), String> {
-    todo!("Ability to read files will be added in future dlc for only $99.99!")
+fn write_output<W: Write>(output: &mut W, text: &str) -> Result<(), String> {
+    match output.write_all(text.as_bytes()) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(String::from("Failed to write output")),
+    }
+}
+
+fn run_file(path: &str) -> Result<(), String> {
+    let src = match fs::read_to_string(path) {
+        Ok(src) => src,
+        Err(e) => return Err(format!("Could not read '{path}': {e}")),
+    };
+    run(&src, &mut io::stdout(), false)
 }
 
-fn run(src: &str) {
-    let mut scanner = Scanner::new(src);
-    let tokens: Vec<Token> = scanner.scan_tokens();
+fn run<W: Write>(src: &str, output: &mut W, repr: bool) -> Result<(), String> {
+    let expr = ic_rs_lang::run(src)?;
+    let text = if repr {expr.to_repr()} else {expr.to_string()};
+    write_output(output, &format!("Parsed Expr is: {text}\n"))
+}
+
+const USAGE: &str = "Usage: lox [options] [script]
+
+Options:
+    -h, --help       Print this message and exit
+    -V, --version    Print the interpreter version and exit
+    --precision N    Display numbers with N decimal places
+
+With no script, an interactive prompt is started.";
 
-    for tok in &tokens {
-        println!("{}", tok.to_string());
+const EX_USAGE: i32 = 64;
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Repl,
+    File(String),
+    Help,
+    Version,
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct Options {
+    precision: Option<usize>,
+}
+
+fn parse_args(args: &[String]) -> Result<(Command, Options), String> {
+    let mut script: Option<String> = None;
+    let mut options = Options::default();
+    let mut args = args.iter().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok((Command::Help, options)),
+            "-V" | "--version" => return Ok((Command::Version, options)),
+            "--precision" => {
+                let precision = match args.next().map(|n| n.parse::<usize>()) {
+                    Some(Ok(n)) => n,
+                    _ => return Err(String::from("--precision expects a non-negative integer")),
+                };
+                options.precision = Some(precision);
+            }
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{arg}'")),
+            _ => {
+                if script.is_some() {
+                    return Err(String::from("Only one script may be given"));
+                }
+                script = Some(arg.to_string());
+            }
+        }
     }
 
-    let mut parser = Parser::new(tokens);
-    let expr = parser.parse();
-    println!("Parsed Expr is: {}", expr.to_string())
+    match script {
+        Some(path) => Ok((Command::File(path), options)),
+        None => Ok((Command::Repl, options)),
+    }
+}
+
+fn run_cli(args: &[String]) -> i32 {
+    let (command, options) = match parse_args(args) {
+        Ok(parsed) => parsed,
+        Err(msg) => {
+            eprintln!("[Error] {msg}\n\n{USAGE}");
+            return EX_USAGE;
+        }
+    };
+
+    set_number_precision(options.precision);
+
+    let result = match command {
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Command::Version => {
+            println!("lox {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        Command::Repl => run_prompt(&ReplConfig::from_env()),
+        Command::File(path) => run_file(&path),
+    };
+
+    match result {
+        Ok(_) => 0,
+        Err(msg) => {
+            eprintln!("Error {msg}");
+            1
+        }
+    }
 }
 
 fn main() {
     let args: Vec<String> = env::args().collect();
+    exit(run_cli(&args))
+}
 
-    for arg in &args {
-        println!("{arg}");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_prompt() {
+        let config = ReplConfig { prompt: String::from("lox> "), repr: false };
+        let mut output = Vec::new();
+
+        run_repl("1 + 2\n".as_bytes(), &mut output, &config).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("lox> ").count(), 2);
+        assert!(!output.contains("\n> "));
     }
 
-    match args.len() {
-        1 => match run_prompt() {
-            Ok(_) => exit(0),
-            Err(msg) => eprintln!("Error {msg}"),
-        },
-        2 => match run_file(&args[1]) {
-            Ok(_) => exit(0),
-            Err(msg) => eprintln!("Error {msg}"),
-        },
-        _ => {
-            eprintln!("[Error] please use as lox ___");
-            exit(1)
-        }
+    #[test]
+    fn test_repr_output() {
+        let mut config = ReplConfig { prompt: String::from("> "), repr: false };
+        let mut output = Vec::new();
+        run_repl("\"hi\"\n".as_bytes(), &mut output, &config).unwrap();
+        assert!(String::from_utf8(output).unwrap().contains("Parsed Expr is: hi\n"));
+
+        config.repr = true;
+        let mut output = Vec::new();
+        run_repl("\"hi\"\n(\n".as_bytes(), &mut output, &config).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Parsed Expr is: \"hi\"\n"));
+        assert!(output.contains("Error Line 1, column 2: Expected expression"));
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn parse_command(list: &[&str]) -> Result<Command, String> {
+        parse_args(&args(list)).map(|(command, _)| command)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_command(&["lox"]), Ok(Command::Repl));
+        assert_eq!(parse_command(&["lox", "test/ex.lox"]), Ok(Command::File(String::from("test/ex.lox"))));
+        assert_eq!(parse_command(&["lox", "--help"]), Ok(Command::Help));
+        assert!(parse_command(&["lox", "a.lox", "b.lox"]).is_err());
+    }
+
+    #[test]
+    fn test_precision_flag() {
+        let (command, options) = parse_args(&args(&["lox", "--precision", "4", "test/ex.lox"])).unwrap();
+        assert_eq!(command, Command::File(String::from("test/ex.lox")));
+        assert_eq!(options.precision, Some(4));
+
+        assert_eq!(parse_args(&args(&["lox"])).unwrap().1.precision, None);
+        assert!(parse_command(&["lox", "--precision"]).is_err());
+        assert!(parse_command(&["lox", "--precision", "-1"]).is_err());
+    }
+
+    #[test]
+    fn test_version() {
+        assert_eq!(parse_command(&["lox", "--version"]), Ok(Command::Version));
+        assert_eq!(run_cli(&args(&["lox", "--version"])), 0);
+    }
+
+    #[test]
+    fn test_unknown_flag() {
+        assert!(parse_command(&["lox", "--bogus"]).is_err());
+        assert_eq!(run_cli(&args(&["lox", "--bogus"])), EX_USAGE);
+    }
+
+    #[test]
+    fn test_run_file() {
+        assert_eq!(run_cli(&args(&["lox", "test/missing.lox"])), 1);
+        assert!(run_file("test/missing.lox").unwrap_err().starts_with("Could not read 'test/missing.lox'"));
+
+        let path = env::temp_dir().join("lox_test_run_file.lox");
+        fs::write(&path, "1 + 2").unwrap();
+        assert_eq!(run_cli(&args(&["lox", path.to_str().unwrap()])), 0);
+        fs::write(&path, "\"unterminated").unwrap();
+        assert_eq!(run_cli(&args(&["lox", path.to_str().unwrap()])), 1);
+        fs::remove_file(&path).unwrap();
     }
 }
@@ -10,83 +10,122 @@ impl Parser {
         Self {tokens, current: 0}
     }
 
-    pub fn parse(&mut self) -> Expr {
+    pub fn parse(&mut self) -> Result<Expr, String> {
         self.expression()
     }
 
-    fn expression(&mut self) -> Expr {
-        self.equality()
+    fn expression(&mut self) -> Result<Expr, String> {
+        self.ternary()
+    }
+
+    fn ternary(&mut self) -> Result<Expr, String> {
+        let condition = self.equality()?;
+
+        if self.match_tokens(&[Type::Question]) {
+            let then = self.expression()?;
+            self.consume(Type::Colon, "Expected ':' in conditional expression")?;
+            let otherwise = self.ternary()?;
+            return Ok(Expr::new_ternary(condition, then, otherwise));
+        }
+
+        Ok(condition)
     }
     
-    fn equality(&mut self) -> Expr {
-        let mut expr = self.comparison();
+    fn equality(&mut self) -> Result<Expr, String> {
+        let mut expr = self.bitwise()?;
 
         while self.match_tokens(&[Type::BangEqual, Type::EqualEqual]) {
             let operator = self.previous();
-            let right = self.comparison();
+            let right = self.bitwise()?;
             expr = Expr::new_binary(expr, operator, right);
         }
 
-        expr
+        Ok(expr)
     }
 
-    fn comparison(&mut self) -> Expr {
-        let mut expr = self.term();
+    fn bitwise(&mut self) -> Result<Expr, String> {
+        let mut expr = self.comparison()?;
+
+        while self.match_tokens(&[Type::Ampersand, Type::Pipe, Type::Caret, Type::LessLess, Type::GreaterGreater]) {
+            let operator = self.previous();
+            let right = self.comparison()?;
+            expr = Expr::new_binary(expr, operator, right);
+        }
+
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
 
         while self.match_tokens(&[Type::Greater, Type::GreaterEqual, Type::Less, Type::LessEqual]) {
             let operator = self.previous();
-            let right = self.term();
+            let right = self.term()?;
             expr = Expr::new_binary(expr, operator, right);
         }
 
-        expr
+        Ok(expr)
     }
 
-    fn term(&mut self) -> Expr {
-        let mut expr = self.factor();
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut expr = self.factor()?;
 
         while self.match_tokens(&[Type::Minus, Type::Plus]) {
             let operator = self.previous();
-            let right = self.factor();
+            let right = self.factor()?;
             expr = Expr::new_binary(expr, operator, right);
         }
 
-        expr
+        Ok(expr)
     }
 
-    fn factor(&mut self) -> Expr {
-        let mut expr = self.unary();
+    fn factor(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
 
         while self.match_tokens(&[Type::Slash, Type::Star]) {
             let operator = self.previous();
-            let right = self.factor();
+            let right = self.unary()?;
             expr = Expr::new_binary(expr, operator, right);
         }
 
-        expr
+        Ok(expr)
     }
 
-    fn unary(&mut self) -> Expr {
+    fn unary(&mut self) -> Result<Expr, String> {
         if self.match_tokens(&[Type::Bang, Type::Minus]) {
             let operator = self.previous();
-            let right = self.unary();
-            return Expr::new_unary(operator, right);
+            let right = self.unary()?;
+            return Ok(Expr::new_unary(operator, right));
+        }
+
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Expr, String> {
+        let expr = self.primary()?;
+
+        // The right operand may itself be a power or a negation, making ** right-associative
+        if self.match_tokens(&[Type::StarStar]) {
+            let operator = self.previous();
+            let right = self.unary()?;
+            return Ok(Expr::new_binary(expr, operator, right));
         }
 
-        self.primary()
+        Ok(expr)
     }
 
-    fn primary(&mut self) -> Expr {
+    fn primary(&mut self) -> Result<Expr, String> {
         if self.match_tokens(&[Type::LeftParen]) {
-            let expr = self.expression();
-            self.consume(Type::RightParen, "Expected ')'");
-            return Expr::new_grouping(expr)
+            let expr = self.expression()?;
+            self.consume(Type::RightParen, "Expected ')'")?;
+            return Ok(Expr::new_grouping(expr))
+        }
+
+        if self.match_tokens(&[Type::Number, Type::String, Type::Identifier, Type::True, Type::False, Type::Nil]) {
+            return Ok(Expr::new_literal(LitValue::from_token(self.previous())));
         }
-        
-        let expr = Expr::new_literal(LitValue::from_token(self.peek()));
-        self.advance();
-        expr
 
+        Err(self.expected_error("Expected expression"))
     }
 
     fn match_tokens(&mut self, types: &[Type]) -> bool {
@@ -101,9 +140,41 @@ impl Parser {
         false
     }
 
-    fn consume(&mut self, typ: Type, msg: &str) {
-        if self.peek().get_type() == typ {self.advance();}
-        else {panic!("{}", msg);}
+    fn consume(&mut self, typ: Type, msg: &str) -> Result<Token, String> {
+        if self.peek().get_type() == typ {return Ok(self.advance())}
+        Err(self.expected_error(msg))
+    }
+
+    fn expected_error(&mut self, msg: &str) -> String {
+        let found = self.peek();
+        let found = match found.get_type() {
+            Type::Eof => String::from("end of input"),
+            typ => format!("'{}' ({})", found.get_lexeme(), typ.to_string())
+        };
+
+        // Point at the offending token, or just past the previous one if it ended the line
+        let here = self.peek();
+        let (line, column) = match self.current {
+            0 => (here.get_line(), here.get_column()),
+            _ => {
+                let (end_line, end_column) = Self::end_of(&self.previous());
+                if here.get_type() != Type::Eof && here.get_line() == end_line {
+                    (here.get_line(), here.get_column())
+                } else {
+                    (end_line, end_column)
+                }
+            }
+        };
+        format!("Line {line}, column {column}: {msg}, found {found}")
+    }
+
+    // Line and column just past the end of a token, which may span lines (e.g. a multi-line string)
+    fn end_of(token: &Token) -> (usize, usize) {
+        let lexeme = token.get_lexeme();
+        match lexeme.rfind('\n') {
+            Some(i) => (token.get_line() + lexeme.matches('\n').count(), lexeme[i + 1..].chars().count() + 1),
+            None => (token.get_line(), token.get_column() + lexeme.chars().count())
+        }
     }
 
     fn advance(&mut self) -> Token {
@@ -129,4 +200,124 @@ impl Parser {
         self.tokens[self.current - 1].to_owned()
     }
     
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::Scanner;
+
+    fn try_parse(src: &str) -> Result<Expr, String> {
+        Parser::new(Scanner::new(src).into_tokens().unwrap()).parse()
+    }
+
+    fn parse(src: &str) -> Expr {
+        try_parse(src).unwrap()
+    }
+
+    fn assert_left_associative(src: &str) {
+        match parse(src) {
+            Expr::Binary { left, right, .. } => {
+                assert!(matches!(*left, Expr::Binary { .. }), "{src} should group to the left");
+                assert!(matches!(*right, Expr::Literal { .. }), "{src} should group to the left");
+            },
+            _ => panic!("{src} should parse as a binary expression")
+        }
+    }
+
+    #[test]
+    fn test_factor_is_left_associative() {
+        assert_left_associative("8 / 4 / 2");
+        assert_left_associative("2 * 3 * 4");
+        assert_left_associative("8 / 4 * 2");
+        assert_left_associative("8 - 4 - 2");
+    }
+
+    #[test]
+    fn test_power() {
+        match parse("-2 ** 2") {
+            Expr::Unary { right, .. } => assert_eq!(right.to_string(), "2 ** 2"),
+            _ => panic!("-2 ** 2 should negate the power")
+        }
+
+        match parse("2 ** 2 ** 3") {
+            Expr::Binary { left, right, .. } => {
+                assert!(matches!(*left, Expr::Literal { .. }));
+                assert_eq!(right.to_string(), "2 ** 3");
+            },
+            _ => panic!("2 ** 2 ** 3 should group to the right")
+        }
+
+        match parse("2 * 3 ** 2") {
+            Expr::Binary { operator, right, .. } => {
+                assert_eq!(operator.get_type(), Type::Star);
+                assert_eq!(right.to_string(), "3 ** 2");
+            },
+            _ => panic!("** should bind tighter than *")
+        }
+
+        assert!(matches!(parse("2 ** -1"), Expr::Binary { right, .. } if matches!(*right, Expr::Unary { .. })));
+    }
+
+    fn top_operator(src: &str) -> Type {
+        match parse(src) {
+            Expr::Binary { operator, .. } => operator.get_type(),
+            _ => panic!("{src} should parse as a binary expression")
+        }
+    }
+
+    #[test]
+    fn test_bitwise_precedence() {
+        assert_left_associative("1 | 2 & 3");
+        assert_left_associative("1 << 2 >> 3");
+        assert_eq!(top_operator("1 == 2 & 3"), Type::EqualEqual);
+        assert_eq!(top_operator("1 ^ 2 < 3"), Type::Caret);
+        assert_eq!(top_operator("1 < 2 ^ 3"), Type::Caret);
+    }
+
+    #[test]
+    fn test_ternary() {
+        assert_eq!(parse("1 < 2 ? 3 + 4 : 5").to_string(), "1 < 2 ? 3 + 4 : 5");
+
+        match parse("true ? 1 : false ? 2 : 3") {
+            Expr::Ternary { condition, otherwise, .. } => {
+                assert_eq!(condition.to_string(), "true");
+                assert!(matches!(*otherwise, Expr::Ternary { .. }));
+            },
+            _ => panic!("expected a ternary expression")
+        }
+    }
+
+    #[test]
+    fn test_ternary_missing_colon() {
+        assert_eq!(try_parse("true ? 1 2").err().unwrap(), "Line 1, column 10: Expected ':' in conditional expression, found '2' (Number)");
+    }
+
+    #[test]
+    fn test_missing_paren_at_end() {
+        assert_eq!(try_parse("(1 + 2").err().unwrap(), "Line 1, column 7: Expected ')', found end of input");
+    }
+
+    #[test]
+    fn test_missing_paren_reports_found_token() {
+        assert_eq!(try_parse("(1 + 2 3)").err().unwrap(), "Line 1, column 8: Expected ')', found '3' (Number)");
+    }
+
+    #[test]
+    fn test_missing_paren_at_end_of_line() {
+        assert_eq!(try_parse("(1 + 2\n3)").err().unwrap(), "Line 1, column 7: Expected ')', found '3' (Number)");
+    }
+
+    #[test]
+    fn test_missing_expression() {
+        assert_eq!(try_parse("1 +").err().unwrap(), "Line 1, column 4: Expected expression, found end of input");
+        assert_eq!(try_parse("1 + )").err().unwrap(), "Line 1, column 5: Expected expression, found ')' (Right Parenthesis)");
+        assert!(matches!(parse("nil"), Expr::Literal { literal: LitValue::Nil }));
+    }
+
+    #[test]
+    fn test_error_after_multi_line_token() {
+        assert_eq!(try_parse("(\"a\nbc\"").err().unwrap(), "Line 2, column 4: Expected ')', found end of input");
+        assert_eq!(try_parse("(\"a\nbc\" 1)").err().unwrap(), "Line 2, column 5: Expected ')', found '1' (Number)");
+    }
+}
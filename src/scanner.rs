@@ -1,14 +1,20 @@
 use std::collections::HashMap;
+use std::num::IntErrorKind;
 use lazy_static::*;
 
 use crate::token::{Token, TokenType as TType, Literal};
 
 pub struct Scanner {
-    src: String,
+    src: Vec<char>,
     tokens: Vec<Token>,
     start: usize,
     current: usize,
     line: usize,
+    line_start: usize,
+    token_line: usize,
+    token_column: usize,
+    preserve_comments: bool,
+    errors: Vec<String>,
 }
 
 lazy_static! {
@@ -38,23 +44,49 @@ lazy_static! {
 impl Scanner {
     pub fn new(src: &str) -> Self {
         Self {
-            src: src.to_string(),
+            src: src.chars().collect(),
             tokens: Vec::new(),
             start: 0,
             current: 0,
             line: 1,
+            line_start: 0,
+            token_line: 1,
+            token_column: 1,
+            preserve_comments: false,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn new_preserving_comments(src: &str) -> Self {
+        Self {
+            preserve_comments: true,
+            ..Self::new(src)
         }
     }
 
     pub fn scan_tokens(&mut self) -> Vec<Token> {
+        self.scan();
+        self.tokens.clone()
+    }
+
+    pub fn into_tokens(mut self) -> Result<Vec<Token>, String> {
+        self.scan();
+        if self.had_error() {
+            return Err(self.errors.join("\n"));
+        }
+        Ok(self.tokens)
+    }
+
+    fn scan(&mut self) {
         while !self.at_end() {
             self.start = self.current;
+            self.mark_token_start();
             self.scan_token();
         }
 
-        let eof_token=  Token::new(TType::Eof, "", Literal::Null, self.line);
+        self.mark_token_start();
+        let eof_token=  Token::new(TType::Eof, "", Literal::Null, self.token_line, self.token_column);
         self.tokens.push(eof_token);
-        self.tokens.clone()
     }
 
     fn at_end(&self) -> bool {
@@ -69,11 +101,28 @@ impl Scanner {
             '{' => self.add_token_t(TType::LeftBrace),
             '}' => self.add_token_t(TType::RightBrace),
             ',' => self.add_token_t(TType::Comma),
-            '.' => self.add_token_t(TType::Dot),
+            '.' => {
+                if self.peek(0).is_ascii_digit() {
+                    self.number();
+                } else {
+                    self.add_token_t(TType::Dot);
+                }
+            },
             '-' => self.add_token_t(TType::Minus),
             '+' => self.add_token_t(TType::Plus),
             ';' => self.add_token_t(TType::SemiColon),
-            '*' => self.add_token_t(TType::Star),
+            '*' => {
+                if self.expect('*') {
+                    self.add_token_t(TType::StarStar);
+                } else {
+                    self.add_token_t(TType::Star);
+                }
+            },
+            '?' => self.add_token_t(TType::Question),
+            ':' => self.add_token_t(TType::Colon),
+            '&' => self.add_token_t(TType::Ampersand),
+            '|' => self.add_token_t(TType::Pipe),
+            '^' => self.add_token_t(TType::Caret),
             '!' => { 
                 if self.expect('=') {
                     self.add_token_t(TType::BangEqual);
@@ -91,6 +140,8 @@ impl Scanner {
             '<' => { 
                 if self.expect('=') {
                     self.add_token_t(TType::LessEqual);
+                } else if self.expect('<') {
+                    self.add_token_t(TType::LessLess);
                 } else {
                     self.add_token_t(TType::Less);
                 }
@@ -98,13 +149,15 @@ impl Scanner {
             '>' => { 
                 if self.expect('=') {
                     self.add_token_t(TType::GreaterEqual);
+                } else if self.expect('>') {
+                    self.add_token_t(TType::GreaterGreater);
                 } else {
                     self.add_token_t(TType::Greater);
                 }
             },
             '/' => {
                 if self.expect('/') {
-                    while self.peek(0) != '\n' && !self.at_end() {self.advance();}
+                    self.single_line_comment();
                 } else if self.expect('*'){
                     self.multiline_comment();
                 } else {
@@ -113,22 +166,28 @@ impl Scanner {
             }
             '"' => self.string(),
             ' ' => (),
+            '\t' => (),
             '\r' => (),
-            '\n' => self.current += 1,
+            '\n' => self.new_line(),
             _ => {
                 if c.is_ascii_digit() {
-                    self.number();
+                    match (c, self.peek(0)) {
+                        ('0', 'x' | 'X') => self.radix_number(16),
+                        ('0', 'o' | 'O') => self.radix_number(8),
+                        ('0', 'b' | 'B') => self.radix_number(2),
+                        _ => self.number()
+                    }
                 } else if c.is_ascii_alphabetic() || c == '_'{
                     self.identifier();
                 } else {
-                    self.error("", "Unexpected Character {c}")
+                    self.error(&format!("Unexpected character {c:?}"))
                 }
             }
         }
     }
 
     fn advance(&mut self) -> char {
-        let c = self.src.chars().nth(self.current).unwrap();
+        let c = self.src[self.current];
         self.current += 1;
         c
     }
@@ -136,7 +195,7 @@ impl Scanner {
     fn expect(&mut self, expected: char) -> bool {
         if self.at_end() { return false; }
 
-        if self.src.chars().nth(self.current).unwrap() != expected {
+        if self.src[self.current] != expected {
             return false;
         }
 
@@ -149,42 +208,166 @@ impl Scanner {
     }
 
     fn add_token(&mut self, ttype: TType, literal: Literal) {
-        let text = &self.src[self.start..self.current];
-        self.tokens.push(Token::new(ttype, text, literal, self.line));
+        let text = self.text(self.start, self.current);
+        self.tokens.push(Token::new(ttype, &text, literal, self.token_line, self.token_column));
+    }
+
+    fn mark_token_start(&mut self) {
+        self.token_line = self.line;
+        self.token_column = self.current - self.line_start + 1;
+    }
+
+    fn new_line(&mut self) {
+        self.line += 1;
+        self.line_start = self.current;
     }
 
     fn number(&mut self) {
-        while self.peek(0).is_ascii_digit() {self.advance();}
+        let mut valid = self.digits();
 
-        if self.peek(0) == '.' && self.peek(1).is_ascii_digit(){
+        // A number that started with '.' (like .5) has already had its fraction scanned
+        if self.src[self.start] != '.' && self.peek(0) == '.' && self.peek(1).is_ascii_digit(){
             self.advance();
-            while self.peek(0).is_ascii_digit() {self.advance();}
+            valid &= self.digits();
         }
 
-        self.add_token(TType::Number, Literal::Numeric(self.src[self.start..self.current].parse::<f64>().unwrap()))
+        if matches!(self.peek(0), 'e' | 'E') {
+            self.advance();
+            if matches!(self.peek(0), '+' | '-') {self.advance();}
+
+            if !self.peek(0).is_ascii_digit() {
+                self.error("Expected digits in number exponent");
+                return;
+            }
+            valid &= self.digits();
+        }
+
+        if !valid {
+            self.error("Digit separators '_' must sit between two digits");
+            return;
+        }
+
+        let text = self.text(self.start, self.current).replace('_', "");
+        self.add_token(TType::Number, Literal::Numeric(text.parse::<f64>().unwrap()))
     }
 
-    pub fn error(&self, loc: &str, msg: &str) {
-        eprintln!("Error at line {0}, {loc}: {msg}", self.line)
+    // Consumes a run of digits, returning false if a '_' separator isn't followed by a digit
+    fn digits(&mut self) -> bool {
+        let mut valid = true;
+        while self.peek(0).is_ascii_digit() || self.peek(0) == '_' {
+            if self.advance() == '_' && !self.peek(0).is_ascii_digit() {
+                valid = false;
+            }
+        }
+        valid
+    }
+
+    fn radix_number(&mut self, radix: u32) {
+        let prefix = self.advance();
+        let mut valid = true;
+
+        // Like digits(), a '_' separator must sit between two digits
+        while self.peek(0).is_ascii_alphanumeric() || self.peek(0) == '_' {
+            let first = self.current == self.start + 2;
+            if self.advance() == '_' && (first || !self.peek(0).is_ascii_alphanumeric()) {
+                valid = false;
+            }
+        }
+
+        if !valid {
+            self.error("Digit separators '_' must sit between two digits");
+            return;
+        }
+
+        let digits = self.text(self.start + 2, self.current).replace('_', "");
+        let name = match radix {
+            16 => "hexadecimal",
+            8  => "octal",
+            _  => "binary"
+        };
+
+        match u64::from_str_radix(&digits, radix) {
+            Ok(n) => self.add_token(TType::Number, Literal::Numeric(n as f64)),
+            Err(e) if *e.kind() == IntErrorKind::PosOverflow => {
+                self.error(&format!("Out of range {name} literal '0{prefix}{digits}'"))
+            },
+            Err(_) => self.error(&format!("Invalid {name} literal '0{prefix}{digits}'"))
+        }
+    }
+
+    // Errors are collected rather than printed so into_tokens can hand them back to the caller
+    pub fn error(&mut self, msg: &str) {
+        self.error_at(self.token_line, self.token_column, msg);
+    }
+
+    // For errors found partway through a token, such as a bad escape inside a multi-line string
+    fn error_here(&mut self, msg: &str) {
+        self.error_at(self.line, self.current - self.line_start, msg);
+    }
+
+    fn error_at(&mut self, line: usize, column: usize, msg: &str) {
+        self.errors.push(format!("Line {line}, column {column}: {msg}"));
+    }
+
+    pub fn had_error(&self) -> bool {
+        !self.errors.is_empty()
     }
     
     fn peek(&self, n: usize) -> char {
         if self.current + n >= self.src.len() { return '\0'}
-        return self.src.chars().nth(self.current + n).unwrap()
+        return self.src[self.current + n]
+    }
+
+    fn text(&self, start: usize, end: usize) -> String {
+        self.src[start..end].iter().collect()
     }
     
     fn string(&mut self) {
+        let mut literal = String::new();
+
         while self.peek(0) != '"' && !self.at_end() {
-            if self.peek(0) == '\n' {self.line += 1}
-            if self.at_end() {self.error("Here", "Unterminated String")}
-            self.advance();
+            let c = self.advance();
+            match c {
+                '\\' => {
+                    if let Some(escaped) = self.escape() {
+                        literal.push(escaped);
+                    }
+                },
+                '\n' => {
+                    self.new_line();
+                    literal.push(c);
+                },
+                _ => literal.push(c)
+            }
         }
 
-        self.advance();
+        if self.at_end() {
+            self.error("Unterminated String");
+            return;
+        }
 
-        let literal = self.src[self.start + 1..self.current - 1].to_string();
+        self.advance();
         self.add_token(TType::String, Literal::Str(literal));
     }
+
+    fn escape(&mut self) -> Option<char> {
+        if self.at_end() { return None }
+
+        match self.advance() {
+            'n'  => Some('\n'),
+            't'  => Some('\t'),
+            'r'  => Some('\r'),
+            '\\' => Some('\\'),
+            '"'  => Some('"'),
+            '0'  => Some('\0'),
+            'u'  => self.unicode_escape(),
+            c    => {
+                self.error_here(&format!("Unknown escape sequence '\\' followed by {c:?}"));
+                if c == '\n' {self.new_line();}
+                None
+            }
+        }
+    }
     
     fn identifier(&mut self) {
         let mut c = self.peek(0);
@@ -192,23 +375,82 @@ impl Scanner {
             self.advance();
             c = self.peek(0);
         }
-        let s = &self.src[self.start..self.current];
+        let s = self.text(self.start, self.current);
 
-        let token_type = match KEYWORD_MAP.get(&s) {
+        let token_type = match KEYWORD_MAP.get(s.as_str()) {
             Some(t) => t.to_owned(),
             None => TType::Identifier
         };
 
         let literal = match token_type {
-            TType::Identifier => Literal::Id(s.to_string()),
-            _                 => Literal::Keyword(s.to_string())
+            TType::Identifier => Literal::Id(s),
+            _                 => Literal::Keyword(s)
         };
         self.add_token(token_type, literal)
     }
     
+    fn unicode_escape(&mut self) -> Option<char> {
+        // Errors point at the backslash that opens the escape, not at the string's opening quote
+        let (line, column) = (self.line, self.current - self.line_start - 1);
+
+        if !self.expect('{') {
+            self.error_at(line, column, "Expected '{' after '\\u' in unicode escape");
+            return None;
+        }
+
+        let start = self.current;
+        while self.peek(0).is_ascii_hexdigit() {self.advance();}
+        let digits = self.text(start, self.current);
+
+        if !self.expect('}') {
+            self.error_at(line, column, "Expected '}' to close unicode escape");
+            return None;
+        }
+
+        match u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32) {
+            Some(c) => Some(c),
+            None => {
+                self.error_at(line, column, &format!("Invalid unicode escape '\\u{{{digits}}}'"));
+                None
+            }
+        }
+    }
+
+    fn single_line_comment(&mut self) {
+        while self.peek(0) != '\n' && !self.at_end() {self.advance();}
+
+        if self.preserve_comments {
+            let text = self.text(self.start + 2, self.current).trim().to_string();
+            self.add_token(TType::Comment, Literal::Str(text));
+        }
+    }
+
     fn multiline_comment(&mut self) {
-        while self.peek(0) != '*' && !self.at_end() {
-            
+        let mut depth = 1;
+
+        while depth > 0 {
+            if self.at_end() {
+                self.error("Unterminated block comment");
+                return;
+            }
+
+            match self.advance() {
+                '*' if self.peek(0) == '/' => {
+                    self.advance();
+                    depth -= 1;
+                },
+                '/' if self.peek(0) == '*' => {
+                    self.advance();
+                    depth += 1;
+                },
+                '\n' => self.new_line(),
+                _ => ()
+            }
+        }
+
+        if self.preserve_comments {
+            let text = self.text(self.start + 2, self.current - 2).trim().to_string();
+            self.add_token(TType::Comment, Literal::Str(text));
         }
     }
 }
@@ -221,4 +463,241 @@ mod tests {
     fn test_scanner() {
         
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_into_tokens() {
+        let src = "(1 + 2) * \"three\"";
+        let scanned = Scanner::new(src).scan_tokens();
+        let owned = Scanner::new(src).into_tokens().unwrap();
+
+        assert_eq!(owned.len(), 8);
+        assert_eq!(scanned.len(), owned.len());
+        for (a, b) in scanned.iter().zip(owned.iter()) {
+            assert_eq!(a.to_string(), b.to_string());
+        }
+
+        assert!(Scanner::new("\"unterminated").into_tokens().is_err());
+
+        // The returned vector is the scanner's own buffer, not a copy of it
+        let mut scanner = Scanner::new(src);
+        scanner.tokens.reserve(16);
+        let buffer = scanner.tokens.as_ptr();
+        let tokens = scanner.into_tokens().unwrap();
+        assert_eq!(tokens.as_ptr(), buffer);
+    }
+
+    #[test]
+    fn test_scan_errors() {
+        let errors = Scanner::new("1 @ 2\t#").into_tokens().err().unwrap();
+        assert_eq!(errors, "Line 1, column 3: Unexpected character '@'\nLine 1, column 7: Unexpected character '#'");
+    }
+
+    #[test]
+    fn test_preserving_comments() {
+        let src = "// first\n1 + 2 // second";
+        let tokens = Scanner::new_preserving_comments(src).into_tokens().unwrap();
+        let comments: Vec<&Token> = tokens.iter().filter(|t| t.get_type() == TType::Comment).collect();
+
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].get_literal().to_string(), "first");
+        assert_eq!(comments[1].get_literal().to_string(), "second");
+        assert_eq!(comments[1].get_lexeme(), "// second");
+
+        let tokens = Scanner::new(src).into_tokens().unwrap();
+        assert!(tokens.iter().all(|t| t.get_type() != TType::Comment));
+    }
+
+    fn scan_number(src: &str) -> Option<f64> {
+        let mut scanner = Scanner::new(src);
+        let tokens = scanner.scan_tokens();
+        if scanner.had_error() { return None }
+
+        assert_eq!(tokens.len(), 2, "expected a single number in {src}");
+        match tokens[0].get_literal() {
+            Literal::Numeric(n) => Some(n),
+            _ => None
+        }
+    }
+
+    #[test]
+    fn test_hex_numbers() {
+        assert_eq!(scan_number("0xFF"), Some(255.0));
+        assert_eq!(scan_number("0x1a"), Some(26.0));
+        assert_eq!(scan_number("0X10"), Some(16.0));
+        assert_eq!(scan_number("0xG"), None);
+        assert_eq!(scan_number("0x"), None);
+        assert_eq!(scan_number("0xFF_FF"), Some(65535.0));
+        assert_eq!(scan_number("0x_FF"), None);
+        assert_eq!(scan_number("0xFF_"), None);
+        assert_eq!(scan_number("0xF__F"), None);
+
+        assert_eq!(scan_number("0xFFFFFFFFFFFFFFFF"), Some(18446744073709551615.0));
+        let errors = Scanner::new("0x1_0000_0000_0000_0000").into_tokens().err().unwrap();
+        assert_eq!(errors, "Line 1, column 1: Out of range hexadecimal literal '0x10000000000000000'");
+    }
+
+    #[test]
+    fn test_binary_and_octal_numbers() {
+        assert_eq!(scan_number("0b1010"), Some(10.0));
+        assert_eq!(scan_number("0B11"), Some(3.0));
+        assert_eq!(scan_number("0o755"), Some(493.0));
+        assert_eq!(scan_number("0O17"), Some(15.0));
+        assert_eq!(scan_number("0b12"), None);
+        assert_eq!(scan_number("0o8"), None);
+        assert_eq!(scan_number("0b"), None);
+        assert_eq!(scan_number("0o"), None);
+        assert_eq!(scan_number("0b1_0"), Some(2.0));
+        assert_eq!(scan_number("0o7_55"), Some(493.0));
+        assert_eq!(scan_number("0b1_"), None);
+    }
+
+    #[test]
+    fn test_exponent_numbers() {
+        assert_eq!(scan_number("1.5e10"), Some(1.5e10));
+        assert_eq!(scan_number("2E-3"), Some(2e-3));
+        assert_eq!(scan_number("6.022e23"), Some(6.022e23));
+        assert_eq!(scan_number("1e+2"), Some(100.0));
+        assert_eq!(scan_number("1e"), None);
+        assert_eq!(scan_number("1e+"), None);
+    }
+
+    #[test]
+    fn test_digit_separators() {
+        assert_eq!(scan_number("1_000"), Some(1000.0));
+        assert_eq!(scan_number("1_000_000"), Some(1000000.0));
+        assert_eq!(scan_number("1.234_5"), Some(1.2345));
+        assert_eq!(scan_number("1e1_0"), Some(1e10));
+        assert_eq!(scan_number("1_"), None);
+        assert_eq!(scan_number("1__0"), None);
+        assert_eq!(scan_number("1_.5"), None);
+
+        let tokens = Scanner::new("_1").scan_tokens();
+        assert_eq!(tokens[0].get_type(), TType::Identifier);
+    }
+
+    #[test]
+    fn test_token_positions() {
+        let tokens = Scanner::new("var x\n  = \"a\nb\" + 1").scan_tokens();
+        let positions: Vec<(usize, usize)> = tokens.iter().map(|t| (t.get_line(), t.get_column())).collect();
+
+        assert_eq!(positions, vec![(1, 1), (1, 5), (2, 3), (2, 5), (3, 4), (3, 6), (3, 7)]);
+    }
+
+    #[test]
+    fn test_block_comments() {
+        let tokens = Scanner::new("/* a */ 1 /* nested /* b */ still comment */ 2").scan_tokens();
+        let types: Vec<TType> = tokens.iter().map(|t| t.get_type()).collect();
+        assert_eq!(types, vec![TType::Number, TType::Number, TType::Eof]);
+
+        let tokens = Scanner::new("/* one\ntwo */ 3").scan_tokens();
+        assert_eq!(tokens[0].get_line(), 2);
+
+        let tokens = Scanner::new_preserving_comments("/* block */").into_tokens().unwrap();
+        assert_eq!(tokens[0].get_type(), TType::Comment);
+        assert_eq!(tokens[0].get_literal().to_string(), "block");
+    }
+
+    #[test]
+    fn test_unterminated_block_comment() {
+        for src in ["/* no end", "/* open /* inner */", "/*"] {
+            let mut scanner = Scanner::new(src);
+            let tokens = scanner.scan_tokens();
+
+            assert!(scanner.had_error(), "expected an error for {src}");
+            assert_eq!(tokens.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_multibyte_source() {
+        let mut scanner = Scanner::new("\"héllo wörld ✓\" + \"日本\" // ünïcode\n1");
+        let tokens = scanner.scan_tokens();
+
+        assert!(!scanner.had_error());
+        assert_eq!(tokens.len(), 5);
+        assert_eq!(tokens[0].get_literal().to_string(), "héllo wörld ✓");
+        assert_eq!(tokens[0].get_lexeme(), "\"héllo wörld ✓\"");
+        assert_eq!(tokens[1].get_column(), 17);
+        assert_eq!(tokens[2].get_literal().to_string(), "日本");
+        assert_eq!(tokens[3].get_line(), 2);
+    }
+
+    #[test]
+    fn test_leading_decimal_point() {
+        assert_eq!(scan_number(".5"), Some(0.5));
+        assert_eq!(scan_number(".75"), Some(0.75));
+        assert_eq!(scan_number(".5e2"), Some(50.0));
+
+        let types = |src| Scanner::new(src).scan_tokens().iter().map(|t| t.get_type()).collect::<Vec<TType>>();
+        assert_eq!(types("."), vec![TType::Dot, TType::Eof]);
+        assert_eq!(types("1."), vec![TType::Number, TType::Dot, TType::Eof]);
+        assert_eq!(types(".5.5"), vec![TType::Number, TType::Number, TType::Eof]);
+    }
+
+    #[test]
+    fn test_operators() {
+        let types: Vec<TType> = Scanner::new("< <= << > >= >> & | ^ * **").scan_tokens().iter().map(|t| t.get_type()).collect();
+        assert_eq!(types, vec![
+            TType::Less, TType::LessEqual, TType::LessLess,
+            TType::Greater, TType::GreaterEqual, TType::GreaterGreater,
+            TType::Ampersand, TType::Pipe, TType::Caret,
+            TType::Star, TType::StarStar, TType::Eof
+        ]);
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let mut scanner = Scanner::new(r#""line1\nline2\t\"quoted\" \\ \r\0""#);
+        let tokens = scanner.scan_tokens();
+
+        assert!(!scanner.had_error());
+        assert_eq!(tokens[0].get_type(), TType::String);
+        assert_eq!(tokens[0].get_literal().to_string(), "line1\nline2\t\"quoted\" \\ \r\0");
+    }
+
+    #[test]
+    fn test_unicode_escapes() {
+        let mut scanner = Scanner::new(r#""\u{1F600} \u{e9}\u{41}""#);
+        let tokens = scanner.scan_tokens();
+
+        assert!(!scanner.had_error());
+        assert_eq!(tokens[0].get_literal().to_string(), "😀 éA");
+
+        for src in [r#""\u{}""#, r#""\u{D800}""#, r#""\u{110000}""#, r#""\u41""#, r#""\u{41""#] {
+            let mut scanner = Scanner::new(src);
+            scanner.scan_tokens();
+            assert!(scanner.had_error(), "expected an error for {src}");
+        }
+
+        let errors = Scanner::new("\"ab\n\tc \\u{D800}\"").into_tokens().err().unwrap();
+        assert_eq!(errors, "Line 2, column 4: Invalid unicode escape '\\u{D800}'");
+        let errors = Scanner::new("\"x\\u{41\"").into_tokens().err().unwrap();
+        assert_eq!(errors, "Line 1, column 3: Expected '}' to close unicode escape");
+    }
+
+    #[test]
+    fn test_unknown_escape() {
+        let mut scanner = Scanner::new(r#""bad \q escape""#);
+        scanner.scan_tokens();
+        assert!(scanner.had_error());
+
+        let mut scanner = Scanner::new("\"a\\\nb\" x");
+        let tokens = scanner.scan_tokens();
+        let x = tokens.iter().find(|t| t.get_type() == TType::Identifier).unwrap();
+
+        assert!(scanner.had_error());
+        assert_eq!((x.get_line(), x.get_column()), (2, 4));
+
+        let errors = Scanner::new("\"line1\nline2 \\q\"").into_tokens().err().unwrap();
+        assert_eq!(errors, "Line 2, column 8: Unknown escape sequence '\\' followed by 'q'");
+    }
+
+    #[test]
+    fn test_unterminated_string() {
+        let mut scanner = Scanner::new(r#""no end \"#);
+        let tokens = scanner.scan_tokens();
+
+        assert!(scanner.had_error());
+        assert_eq!(tokens.len(), 1);
+    }
+}
@@ -4,12 +4,15 @@ pub enum TokenType {
     // Single-character tokens.
     LeftParen, RightParen, LeftBrace, RightBrace,
     Comma, Dot, Minus, Plus, SemiColon, Slash, Star,
+    Question, Colon, Ampersand, Pipe, Caret,
   
     // One or two character tokens.
     Bang, BangEqual,
+    StarStar,
     Equal, EqualEqual,
     Greater, GreaterEqual,
     Less, LessEqual,
+    LessLess, GreaterGreater,
   
     // Literals.
     Identifier, String, Number,
@@ -17,6 +20,8 @@ pub enum TokenType {
     // Keywords.
     And, Class, Else, False, Fun, For, If, Nil, Or,
     Print, Return, Super, This, True, Var, While,
+
+    Comment,
   
     Eof
 }
@@ -36,14 +41,22 @@ impl TokenType {
             TokenType::SemiColon =>    return "Semicolon",
             TokenType::Slash =>        return "Slash",
             TokenType::Star =>         return "Star",
+            TokenType::Question =>     return "Question",
+            TokenType::Colon =>        return "Colon",
+            TokenType::Ampersand =>    return "Bitwise And",
+            TokenType::Pipe =>         return "Bitwise Or",
+            TokenType::Caret =>        return "Bitwise Xor",
             TokenType::Bang =>         return "Not",
             TokenType::BangEqual =>    return "Not Equal",
+            TokenType::StarStar =>     return "Power",
             TokenType::Equal =>        return "Assignment",
             TokenType::EqualEqual =>   return "Equals",
             TokenType::Greater =>      return "Greater",
             TokenType::GreaterEqual => return "Greater Equal",
             TokenType::Less =>         return "Less",
             TokenType::LessEqual =>    return "Less Equal",
+            TokenType::LessLess =>     return "Shift Left",
+            TokenType::GreaterGreater => return "Shift Right",
             TokenType::Identifier =>   return "Identifier",
             TokenType::String =>       return "String",
             TokenType::Number =>       return "Number",
@@ -63,6 +76,7 @@ impl TokenType {
             TokenType::True =>         return "True",
             TokenType::Var =>          return "Var",
             TokenType::While =>        return "While",
+            TokenType::Comment =>      return "Comment",
             TokenType::Eof =>          return "Eof",
         }
     }
@@ -94,17 +108,19 @@ pub struct Token {
     token_type: TokenType,
     lexeme: String,
     literal: Literal,
-    line: usize
+    line: usize,
+    column: usize
 }
 
 impl Token {
-    pub fn new(token_type: TokenType, lexeme: &str, literal: Literal, line: usize) -> Self {
-        Self { token_type, lexeme: lexeme.to_string(), literal, line}
+    pub fn new(token_type: TokenType, lexeme: &str, literal: Literal, line: usize, column: usize) -> Self {
+        Self { token_type, lexeme: lexeme.to_string(), literal, line, column}
     }
 
     pub fn to_string(&self) -> String {
         let s = "Token => Type: ".to_owned() + self.token_type.to_string() + " Lexeme: " +
-                        &self.lexeme + " Literal: " + self.literal.to_string() + " at Line: " + &self.line.to_string();
+                        &self.lexeme + " Literal: " + self.literal.to_string() + " at Line: " + &self.line.to_string() +
+                        " Column: " + &self.column.to_string();
         
         s
     }
@@ -120,4 +136,12 @@ impl Token {
     pub fn get_literal(&self) ->  Literal {
         self.literal.clone()
     }
+
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
 }
\ No newline at end of file